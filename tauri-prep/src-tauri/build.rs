@@ -1,7 +1,210 @@
+use std::collections::HashMap;
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
-fn main() {
+use anyhow::{Context, Result};
+
+#[path = "build_support.rs"]
+mod build_support;
+use build_support::{exe_suffix, parse_sums_manifest, sha256_hex, verify_named_sidecars, SumsEntry};
+
+/// Loads `binaries/{name}.sums` if it exists; a sidecar with no manifest simply isn't checked.
+fn load_sums_manifest(binaries_dir: &Path, name: &str) -> Result<Option<HashMap<String, SumsEntry>>> {
+    let sums_path = binaries_dir.join(format!("{}.sums", name));
+    if !sums_path.exists() {
+        return Ok(None);
+    }
+    parse_sums_manifest(&sums_path).map(Some)
+}
+
+/// A sidecar binary discovered in `binaries/` for the current target triple, e.g. filename
+/// `multicorpus-x86_64-pc-windows-msvc.exe` with logical `name` `multicorpus`.
+struct DiscoveredSidecar {
+    name: String,
+    filename: String,
+}
+
+/// Scans `binaries/` for files ending in `-{target_triple}` (or `.exe`) and recovers each
+/// sidecar's logical name.
+fn discover_sidecars(binaries_dir: &Path, target_triple: &str) -> Result<Vec<DiscoveredSidecar>> {
+    if !binaries_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let suffix_exe = format!("-{}.exe", target_triple);
+    let suffix_plain = format!("-{}", target_triple);
+
+    let mut sidecars = Vec::new();
+    for entry in fs::read_dir(binaries_dir).with_context(|| format!("reading {:?}", binaries_dir))? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Some(filename) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let name = if let Some(stripped) = filename.strip_suffix(&suffix_exe) {
+            stripped
+        } else if let Some(stripped) = filename.strip_suffix(&suffix_plain) {
+            stripped
+        } else {
+            continue;
+        };
+        println!("cargo:rerun-if-changed={}", path.display());
+        sidecars.push(DiscoveredSidecar {
+            name: name.to_string(),
+            filename: filename.to_string(),
+        });
+    }
+    Ok(sidecars)
+}
+
+/// Release URL template for a prebuilt sidecar. `{version}` is the pinned `multicorpus`
+/// revision, `{triple}` the target triple, `{suffix}` the platform executable suffix.
+const SIDECAR_RELEASE_URL_TEMPLATE: &str =
+    "https://github.com/Hsbtqemy/AGRAFES/releases/download/multicorpus-v{version}/multicorpus-{triple}{suffix}";
+
+/// The sidecar revision to fetch, pinned to this crate's own version unless overridden.
+/// Cargo already exposes `CARGO_PKG_VERSION` from `Cargo.toml` to build scripts, so that's
+/// the natural place to pin the sidecar release tag without inventing a second version file.
+fn sidecar_version() -> String {
+    env::var("AGRAFES_SIDECAR_VERSION")
+        .unwrap_or_else(|_| env::var("CARGO_PKG_VERSION").unwrap_or_else(|_| "0.0.0".into()))
+}
+
+/// Downloads the sidecar for `target_triple` into `dest`, writing it atomically (download to a
+/// sibling temp file, then rename) so a crashed or interrupted fetch never leaves a partial
+/// binary at the final path.
+fn fetch_sidecar(dest: &Path, target_triple: &str, suffix: &str) -> Result<()> {
+    let version = sidecar_version();
+    let url = SIDECAR_RELEASE_URL_TEMPLATE
+        .replace("{version}", &version)
+        .replace("{triple}", target_triple)
+        .replace("{suffix}", suffix);
+    println!("cargo:warning=Fetching multicorpus sidecar for {target_triple} from {url}");
+
+    let response = ureq::get(&url)
+        .call()
+        .with_context(|| format!("downloading sidecar from {}", url))?;
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("reading sidecar response body from {}", url))?;
+
+    let tmp_path = dest.with_extension("download");
+    fs::write(&tmp_path, &bytes)
+        .with_context(|| format!("writing downloaded sidecar to {:?}", tmp_path))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("setting executable bit on {:?}", tmp_path))?;
+    }
+    fs::rename(&tmp_path, dest)
+        .with_context(|| format!("moving downloaded sidecar into place at {:?}", dest))?;
+    Ok(())
+}
+
+/// Emits `AGRAFES_COMMIT_HASH`, `AGRAFES_COMMIT_SHORT_HASH` and `AGRAFES_COMMIT_DATE` as
+/// compile-time env vars. No-ops when `.git` isn't present, e.g. on docs.rs.
+fn emit_git_metadata(manifest_dir: &Path) {
+    if env::var_os("DOCS_RS").is_some() {
+        return;
+    }
+    let has_git = std::process::Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(manifest_dir)
+        .output()
+        .is_ok_and(|o| o.status.success());
+    if !has_git {
+        return;
+    }
+
+    let output = std::process::Command::new("git")
+        .args(["log", "-1", "--format=%H%n%h%n%cd", "--date=short"])
+        .current_dir(manifest_dir)
+        .output();
+    let Ok(output) = output else { return };
+    if !output.status.success() {
+        return;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let (Some(hash), Some(short_hash), Some(date)) = (lines.next(), lines.next(), lines.next())
+    else {
+        return;
+    };
+
+    println!("cargo:rustc-env=AGRAFES_COMMIT_HASH={}", hash);
+    println!("cargo:rustc-env=AGRAFES_COMMIT_SHORT_HASH={}", short_hash);
+    println!("cargo:rustc-env=AGRAFES_COMMIT_DATE={}", date);
+}
+
+/// Emits `AGRAFES_SIDECAR_VERSION`, identifying the exact `multicorpus` sidecar bundled into
+/// this build, as `<filename> <size-bytes> <sha256>`.
+fn emit_sidecar_version(src_binary: &Path) -> Result<()> {
+    if !src_binary.exists() {
+        return Ok(());
+    }
+    let metadata = fs::metadata(src_binary)
+        .with_context(|| format!("reading metadata for {:?}", src_binary))?;
+    let digest = sha256_hex(src_binary)?;
+    let filename = src_binary
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("multicorpus");
+    println!(
+        "cargo:rustc-env=AGRAFES_SIDECAR_VERSION={} {} {}",
+        filename,
+        metadata.len(),
+        digest
+    );
+    Ok(())
+}
+
+/// Logical sidecar names known to the build: every `binaries/{name}.sums` manifest present,
+/// whether or not its prebuilt binary is actually committed.
+fn known_sidecar_names(binaries_dir: &Path) -> Result<Vec<String>> {
+    if !binaries_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut names = Vec::new();
+    for entry in fs::read_dir(binaries_dir).with_context(|| format!("reading {:?}", binaries_dir))? {
+        let path = entry?.path();
+        if let Some(name) = path.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_suffix(".sums")) {
+            names.push(name.to_string());
+        }
+    }
+    Ok(names)
+}
+
+/// Copies `name`'s build output from its `CARGO_BIN_FILE_<NAME>` artifact-dependency env var
+/// into `dest`, if such a dependency is configured. Returns `false` otherwise.
+///
+/// Nothing in this crate's `Cargo.toml` declares an artifact dependency today — cargo's
+/// `artifact = "bin"` syntax still requires the nightly-only `-Z bindeps` flag, which the rest
+/// of this crate doesn't opt into. This is dormant support for the env var cargo would set if a
+/// `name = { path = "...", artifact = "bin", target = "target" }` dependency is added later.
+fn copy_artifact_dependency_sidecar(name: &str, dest: &Path) -> Result<bool> {
+    let env_var = format!("CARGO_BIN_FILE_{}", name.to_uppercase().replace('-', "_"));
+    let Some(bin_path) = env::var_os(&env_var) else {
+        return Ok(false);
+    };
+    let tmp_path = dest.with_extension("build");
+    fs::copy(&bin_path, &tmp_path).with_context(|| {
+        format!(
+            "copying {} artifact dependency output {:?} to {:?}",
+            name, bin_path, tmp_path
+        )
+    })?;
+    fs::rename(&tmp_path, dest)
+        .with_context(|| format!("moving built sidecar into place at {:?}", dest))?;
+    Ok(true)
+}
+
+fn run() -> Result<()> {
     println!("cargo:rerun-if-changed=binaries/");
 
     let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
@@ -16,17 +219,61 @@ fn main() {
         .trim()
         .to_string()
     });
-    let src_binary = manifest_dir
-        .join("binaries")
-        .join(format!("multicorpus-{}", target_triple));
-
-    // Copy sidecar to manifest root so tauri_build finds it for externalBin "multicorpus"
-    if src_binary.exists() {
-        let dest_root = manifest_dir.join(format!("multicorpus-{}", target_triple));
-        if let Err(e) = std::fs::copy(&src_binary, &dest_root) {
-            eprintln!("cargo:warning=Could not copy sidecar to {:?}: {}", dest_root, e);
+    let suffix = exe_suffix(&target_triple);
+    let binaries_dir = manifest_dir.join("binaries");
+    let src_binary = binaries_dir.join(format!("multicorpus-{}{}", target_triple, suffix));
+
+    let offline = env::var_os("DOCS_RS").is_some();
+    if !src_binary.exists()
+        && !offline
+        && env::var("AGRAFES_FETCH_SIDECAR").as_deref() == Ok("1")
+    {
+        fs::create_dir_all(&binaries_dir)
+            .with_context(|| format!("creating {:?}", binaries_dir))?;
+        fetch_sidecar(&src_binary, &target_triple, suffix)?;
+    }
+
+    // For any known sidecar name (one with a committed `binaries/{name}.sums`) missing a
+    // prebuilt binary for this triple, check for a cargo artifact dependency built from
+    // source. No-op today since none is declared in Cargo.toml (see
+    // copy_artifact_dependency_sidecar's doc comment).
+    let mut artifact_built: HashMap<String, PathBuf> = HashMap::new();
+    for name in known_sidecar_names(&binaries_dir)? {
+        let filename = format!("{}-{}{}", name, target_triple, suffix);
+        if binaries_dir.join(&filename).exists() {
+            continue;
+        }
+        let dest_root = manifest_dir.join(&filename);
+        if copy_artifact_dependency_sidecar(&name, &dest_root)? {
+            println!("cargo:warning=Built {} sidecar from workspace artifact dependency", name);
+            artifact_built.insert(name, dest_root);
         }
     }
 
+    // Copy every discovered sidecar to the manifest root, where tauri_build finds it for a
+    // matching `externalBin` entry. A new helper tool only needs `toolname-{triple}` dropped
+    // into `binaries/` for this script to pick it up; it is bundled only once it is also listed
+    // under `externalBin` in tauri.conf.json, which currently lists no sidecar.
+    for sidecar in discover_sidecars(&binaries_dir, &target_triple)? {
+        if let Some(sums) = load_sums_manifest(&binaries_dir, &sidecar.name)? {
+            verify_named_sidecars(&binaries_dir, &sidecar.name, &target_triple, &sums)?;
+        }
+        let src = binaries_dir.join(&sidecar.filename);
+        let dest_root = manifest_dir.join(&sidecar.filename);
+        fs::copy(&src, &dest_root)
+            .with_context(|| format!("copying sidecar to {:?}", dest_root))?;
+    }
+
+    emit_git_metadata(&manifest_dir);
+    emit_sidecar_version(artifact_built.get("multicorpus").map(PathBuf::as_path).unwrap_or(&src_binary))?;
+
     tauri_build::build();
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("cargo:warning={:#}", e);
+        std::process::exit(1);
+    }
 }
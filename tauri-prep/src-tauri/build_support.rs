@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+
+/// Executable suffix for the given target triple, mirroring `std::env::consts::EXE_SUFFIX`
+/// but derived from the triple we're building *for* rather than the host we're building *on*.
+pub(crate) fn exe_suffix(target_triple: &str) -> &'static str {
+    if target_triple.contains("windows") {
+        ".exe"
+    } else {
+        ""
+    }
+}
+
+/// One entry from a `binaries/{name}.sums` manifest: a target triple mapped to its expected
+/// SHA-256 digest and an optional detached signature file, relative to `binaries/`.
+pub(crate) struct SumsEntry {
+    pub(crate) sha256: String,
+    pub(crate) signature: Option<String>,
+}
+
+/// Parses a `binaries/{name}.sums` manifest (`<target-triple> <sha256-hex> [signature-file]` per line).
+pub(crate) fn parse_sums_manifest(path: &Path) -> Result<HashMap<String, SumsEntry>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("reading sidecar checksum manifest at {:?}", path))?;
+    let mut entries = HashMap::new();
+    for (lineno, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let triple = fields
+            .next()
+            .with_context(|| format!("{:?}:{}: missing target triple", path, lineno + 1))?;
+        let sha256 = fields
+            .next()
+            .with_context(|| format!("{:?}:{}: missing sha256 digest", path, lineno + 1))?;
+        let signature = fields.next().map(str::to_string);
+        entries.insert(
+            triple.to_string(),
+            SumsEntry {
+                sha256: sha256.to_lowercase(),
+                signature,
+            },
+        );
+    }
+    Ok(entries)
+}
+
+pub(crate) fn sha256_hex(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).with_context(|| format!("reading sidecar binary at {:?}", path))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+pub(crate) fn verify_signature(binary: &Path, binaries_dir: &Path, signature_file: &str) -> Result<()> {
+    let sig_path = binaries_dir.join(signature_file);
+    let status = std::process::Command::new("gpg")
+        .args(["--verify"])
+        .arg(&sig_path)
+        .arg(binary)
+        .status()
+        .with_context(|| format!("running gpg --verify for {:?}", binary))?;
+    if !status.success() {
+        bail!("GPG signature verification failed for sidecar {:?}", binary);
+    }
+    Ok(())
+}
+
+/// Verifies each triple in `sums` against its exact `{name}-{triple}` file, in parallel.
+pub(crate) fn verify_named_sidecars(
+    binaries_dir: &Path,
+    name: &str,
+    target_triple: &str,
+    sums: &HashMap<String, SumsEntry>,
+) -> Result<()> {
+    let current = binaries_dir.join(format!("{}-{}{}", name, target_triple, exe_suffix(target_triple)));
+    if current.exists() && !sums.contains_key(target_triple) {
+        bail!(
+            "no checksum recorded for sidecar {:?} (triple {}); update binaries/{}.sums",
+            current,
+            target_triple,
+            name
+        );
+    }
+
+    let candidates: Vec<(&str, PathBuf)> = sums
+        .keys()
+        .map(|triple| (triple.as_str(), binaries_dir.join(format!("{}-{}{}", name, triple, exe_suffix(triple)))))
+        .filter(|(_, path)| path.exists())
+        .collect();
+
+    candidates.par_iter().try_for_each(|(triple, candidate)| -> Result<()> {
+        let entry = &sums[*triple];
+        let digest = sha256_hex(candidate)?;
+        if digest != entry.sha256 {
+            bail!(
+                "checksum mismatch for sidecar {:?}: expected {}, got {}",
+                candidate,
+                entry.sha256,
+                digest
+            );
+        }
+        if let Some(signature_file) = &entry.signature {
+            verify_signature(candidate, binaries_dir, signature_file)?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exe_suffix_windows_triples() {
+        assert_eq!(exe_suffix("x86_64-pc-windows-msvc"), ".exe");
+        assert_eq!(exe_suffix("aarch64-pc-windows-gnullvm"), ".exe");
+    }
+
+    #[test]
+    fn exe_suffix_non_windows_triples() {
+        assert_eq!(exe_suffix("x86_64-unknown-linux-gnu"), "");
+        assert_eq!(exe_suffix("aarch64-apple-darwin"), "");
+    }
+
+    fn write_sums(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(format!("{}.sums", name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn parse_sums_manifest_skips_blank_and_comment_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_sums(
+            dir.path(),
+            "multicorpus",
+            "# comment\n\nx86_64-unknown-linux-gnu deadbeef\naarch64-apple-darwin cafebabe sig.asc\n",
+        );
+        let entries = parse_sums_manifest(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries["x86_64-unknown-linux-gnu"].sha256, "deadbeef");
+        assert!(entries["x86_64-unknown-linux-gnu"].signature.is_none());
+        assert_eq!(entries["aarch64-apple-darwin"].signature.as_deref(), Some("sig.asc"));
+    }
+
+    #[test]
+    fn parse_sums_manifest_lowercases_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_sums(dir.path(), "multicorpus", "x86_64-unknown-linux-gnu DEADBEEF\n");
+        let entries = parse_sums_manifest(&path).unwrap();
+        assert_eq!(entries["x86_64-unknown-linux-gnu"].sha256, "deadbeef");
+    }
+
+    #[test]
+    fn verify_named_sidecars_ignores_differently_named_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        // "multicorpus-debug-x86_64-unknown-linux-gnu" is a distinct sidecar named
+        // "multicorpus-debug", not a "multicorpus" build for triple "debug-x86_64-unknown-linux-gnu".
+        fs::write(dir.path().join("multicorpus-debug-x86_64-unknown-linux-gnu"), b"unrelated").unwrap();
+        let sums = HashMap::new();
+        verify_named_sidecars(dir.path(), "multicorpus", "x86_64-unknown-linux-gnu", &sums).unwrap();
+    }
+
+    #[test]
+    fn verify_named_sidecars_errors_on_missing_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("multicorpus-x86_64-unknown-linux-gnu"), b"binary").unwrap();
+        let sums = HashMap::new();
+        let err = verify_named_sidecars(dir.path(), "multicorpus", "x86_64-unknown-linux-gnu", &sums).unwrap_err();
+        assert!(err.to_string().contains("no checksum recorded"));
+    }
+
+    #[test]
+    fn verify_named_sidecars_errors_on_checksum_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("multicorpus-x86_64-unknown-linux-gnu"), b"binary").unwrap();
+        let mut sums = HashMap::new();
+        sums.insert(
+            "x86_64-unknown-linux-gnu".to_string(),
+            SumsEntry {
+                sha256: "0".repeat(64),
+                signature: None,
+            },
+        );
+        let err = verify_named_sidecars(dir.path(), "multicorpus", "x86_64-unknown-linux-gnu", &sums).unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"));
+    }
+
+    #[test]
+    fn verify_named_sidecars_passes_on_matching_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary_path = dir.path().join("multicorpus-x86_64-unknown-linux-gnu");
+        fs::write(&binary_path, b"binary").unwrap();
+        let digest = sha256_hex(&binary_path).unwrap();
+        let mut sums = HashMap::new();
+        sums.insert(
+            "x86_64-unknown-linux-gnu".to_string(),
+            SumsEntry {
+                sha256: digest,
+                signature: None,
+            },
+        );
+        verify_named_sidecars(dir.path(), "multicorpus", "x86_64-unknown-linux-gnu", &sums).unwrap();
+    }
+}
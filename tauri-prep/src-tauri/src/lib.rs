@@ -0,0 +1,3 @@
+#[cfg(test)]
+#[path = "../build_support.rs"]
+mod build_support;